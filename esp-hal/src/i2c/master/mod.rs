@@ -111,8 +111,23 @@
 //! The I2C driver also implements [embedded-hal] and [embedded-hal-async]
 //! traits, so you can use it with any crate that supports these traits.
 //!
+//! ### Shared I2C access
+//!
+//! If you have multiple devices on the same I2C bus, possibly driven by
+//! different driver crates, you may want to have a look at the bus sharing
+//! implementations provided by [`embedded-hal-bus`] and
+//! [`embassy-embedded-hal`]. The driver implements `SetConfig`, so each device
+//! can also use its own [`Config`] (e.g. a different frequency) with
+//! `I2cDeviceWithConfig`.
+//!
+//! Devices behind an I2C multiplexer (such as the TCA9548A) can be accessed the
+//! same way, by using a multiplexer driver that implements the [embedded-hal]
+//! I2C traits for each of its channels on top of a shared bus handle.
+//!
 //! [embedded-hal]: embedded_hal::i2c
 //! [embedded-hal-async]: embedded_hal_async::i2c
+//! [`embedded-hal-bus`]: https://docs.rs/embedded-hal-bus/latest/embedded_hal_bus/i2c/index.html
+//! [`embassy-embedded-hal`]: embassy_embedded_hal::shared_bus
 
 use core::{
     marker::PhantomData,
@@ -223,6 +238,12 @@ pub enum BusTimeout {
 
     /// Timeout in bus clock cycles.
     BusCycles(u32),
+
+    /// Timeout as a duration.
+    ///
+    /// The duration is converted to bus clock cycles using the configured bus
+    /// frequency, rounding up.
+    Duration(Duration),
 }
 
 impl BusTimeout {
@@ -233,7 +254,7 @@ impl BusTimeout {
     /// longer (at most ~double) timeouts than configured. We may provide an
     /// `ApbCycles` variant in the future to allow specifying the timeout in
     /// APB cycles directly.
-    fn apb_cycles(self, half_bus_cycle: u32) -> Result<Option<u32>, ConfigError> {
+    fn apb_cycles(self, half_bus_cycle: u32, bus_freq: u32) -> Result<Option<u32>, ConfigError> {
         match self {
             BusTimeout::Maximum => Ok(Some(property!("i2c_master.max_bus_timeout"))),
 
@@ -241,13 +262,17 @@ impl BusTimeout {
             BusTimeout::Disabled => Ok(None),
 
             BusTimeout::BusCycles(cycles) => {
+                let to_peri = cycles
+                    .checked_mul(2 * half_bus_cycle)
+                    .ok_or(ConfigError::TimeoutTooLong)?;
+
                 let raw = if cfg!(i2c_master_bus_timeout_is_exponential) {
-                    let to_peri = (cycles * 2 * half_bus_cycle).max(1);
+                    let to_peri = to_peri.max(1);
                     let log2 = to_peri.ilog2();
                     // If not a power of 2, round up so that we don't shorten timeouts.
                     if to_peri != 1 << log2 { log2 + 1 } else { log2 }
                 } else {
-                    cycles * 2 * half_bus_cycle
+                    to_peri
                 };
 
                 if raw <= property!("i2c_master.max_bus_timeout") {
@@ -256,6 +281,16 @@ impl BusTimeout {
                     Err(ConfigError::TimeoutTooLong)
                 }
             }
+
+            BusTimeout::Duration(duration) => {
+                let cycles = duration
+                    .as_micros()
+                    .saturating_mul(u64::from(bus_freq))
+                    .div_ceil(1_000_000);
+                let cycles = u32::try_from(cycles).map_err(|_| ConfigError::TimeoutTooLong)?;
+
+                BusTimeout::BusCycles(cycles).apb_cycles(half_bus_cycle, bus_freq)
+            }
         }
     }
 }
@@ -269,6 +304,9 @@ impl BusTimeout {
 /// timeout mechanism (like [`embassy_time::with_timeout`]) for better
 /// efficiency.
 ///
+/// When an operation runs into this timeout, the driver clears the bus the same
+/// way [`I2c::clear_bus`] does, so that the next transaction can proceed.
+///
 /// [`embassy_time::with_timeout`]: https://docs.rs/embassy-time/0.4.0/embassy_time/fn.with_timeout.html
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -349,7 +387,7 @@ pub enum Error {
     FifoExceeded,
     /// The acknowledgment check failed.
     AcknowledgeCheckFailed(AcknowledgeCheckFailedReason),
-    /// A timeout occurred during transmission.
+    /// The [`SoftwareTimeout`] expired during transmission.
     Timeout,
     /// The arbitration for the bus was lost.
     ArbitrationLost,
@@ -361,6 +399,10 @@ pub enum Error {
     ZeroLengthInvalid,
     /// The given address is invalid.
     AddressInvalid(I2cAddress),
+    /// The hardware detected that the bus did not make progress for longer
+    /// than the configured bus or FSM timeout, typically because a device
+    /// stretched the clock for too long.
+    BusTimeout,
 }
 
 /// I2C no acknowledge error reason.
@@ -406,6 +448,12 @@ impl From<&AcknowledgeCheckFailedReason> for embedded_hal::i2c::NoAcknowledgeSou
     }
 }
 
+impl Error {
+    fn is_timeout(&self) -> bool {
+        matches!(self, Error::Timeout | Error::BusTimeout)
+    }
+}
+
 impl core::error::Error for Error {}
 
 impl core::fmt::Display for Error {
@@ -415,7 +463,7 @@ impl core::fmt::Display for Error {
             Error::AcknowledgeCheckFailed(reason) => {
                 write!(f, "The acknowledgment check failed. Reason: {reason}")
             }
-            Error::Timeout => write!(f, "A timeout occurred during transmission"),
+            Error::Timeout => write!(f, "The software timeout expired during transmission"),
             Error::ArbitrationLost => write!(f, "The arbitration for the bus was lost"),
             Error::ExecutionIncomplete => {
                 write!(f, "The execution of the I2C command was incomplete")
@@ -427,6 +475,7 @@ impl core::fmt::Display for Error {
             Error::AddressInvalid(address) => {
                 write!(f, "The given address ({address:?}) is invalid")
             }
+            Error::BusTimeout => write!(f, "The bus timed out, possibly due to clock stretching"),
         }
     }
 }
@@ -891,7 +940,7 @@ impl<'a> I2cFuture<'a> {
 
         let result = if self.is_done() {
             // Even though we are done, we have to check for NACK and arbitration loss.
-            let result = if error == Err(Error::Timeout) {
+            let result = if error == Err(Error::BusTimeout) {
                 // We are both done, and timed out. Likely the transaction has completed, but we
                 // checked too late?
                 Ok(())
@@ -938,7 +987,7 @@ impl Drop for I2cFuture<'_> {
     fn drop(&mut self) {
         if !self.finished {
             let result = self.poll_completion();
-            if result.is_pending() || result == Poll::Ready(Err(Error::Timeout)) {
+            if result.is_pending() || matches!(result, Poll::Ready(Err(e)) if e.is_timeout()) {
                 self.driver.reset_fsm(true);
             }
         }
@@ -1124,6 +1173,58 @@ impl<'d> I2c<'d, Async> {
             .await
             .inspect_err(|error| self.internal_recover(error))
     }
+
+    #[procmacros::doc_replace]
+    /// Checks whether a device acknowledges the given `address`.
+    ///
+    /// This is the asynchronous version of [`I2c::probe`].
+    ///
+    /// ## Errors
+    ///
+    /// Errors other than a missing acknowledgement (for example a timeout or
+    /// an invalid address) are returned as they are.
+    ///
+    /// ## Example
+    ///
+    /// ```rust, no_run
+    /// # {before_snippet}
+    /// use esp_hal::i2c::master::{Config, I2c};
+    /// let mut i2c = I2c::new(peripherals.I2C0, Config::default())?
+    ///     .with_sda(peripherals.GPIO1)
+    ///     .with_scl(peripherals.GPIO2)
+    ///     .into_async();
+    ///
+    /// for address in 0x08..0x78 {
+    ///     if i2c.probe_async(address).await? {
+    ///         println!("Found device at {address:#04x}");
+    ///     }
+    /// }
+    /// # {after_snippet}
+    /// ```
+    #[instability::unstable]
+    pub async fn probe_async<A: Into<I2cAddress>>(&mut self, address: A) -> Result<bool, Error> {
+        probe_result(self.write_async(address, &[]).await)
+    }
+
+    /// Clears the bus and resets the I2C controller.
+    ///
+    /// This is the asynchronous version of [`I2c::clear_bus`].
+    #[instability::unstable]
+    pub async fn clear_bus_async(&mut self) {
+        self.driver().clear_bus().await;
+    }
+}
+
+/// Maps the result of an empty write to the result of a probe.
+///
+/// An empty write only transmits the address, so any missing acknowledgement
+/// means that no device answered, even on chips that cannot tell the reason.
+fn probe_result(result: Result<(), Error>) -> Result<bool, Error> {
+    match result {
+        Ok(()) => Ok(true),
+        Err(Error::AcknowledgeCheckFailed(_)) => Ok(false),
+        Err(error) => Err(error),
+    }
 }
 
 impl<'d, Dm> I2c<'d, Dm>
@@ -1141,7 +1242,7 @@ where
     fn internal_recover(&self, error: &Error) {
         // Timeout errors mean our hardware is (possibly) working when it gets reset. Clear the bus
         // in this case, to prevent leaving the I2C device mid-transfer.
-        self.driver().reset_fsm(*error == Error::Timeout)
+        self.driver().reset_fsm(error.is_timeout())
     }
 
     /// Connect a pin to the I2C SDA signal.
@@ -1317,6 +1418,72 @@ where
             .inspect_err(|error| self.internal_recover(error))
     }
 
+    #[procmacros::doc_replace]
+    /// Checks whether a device acknowledges the given `address`.
+    ///
+    /// This performs a zero-length write to `address`, and returns `Ok(true)`
+    /// if the device acknowledged its address, or `Ok(false)` if nothing
+    /// responded. This can be used to scan the bus for connected devices.
+    ///
+    /// ## Errors
+    ///
+    /// Errors other than a missing acknowledgement (for example a timeout or
+    /// an invalid address) are returned as they are.
+    ///
+    /// ## Example
+    ///
+    /// ```rust, no_run
+    /// # {before_snippet}
+    /// use esp_hal::i2c::master::{Config, I2c};
+    /// # let mut i2c = I2c::new(
+    /// #   peripherals.I2C0,
+    /// #   Config::default(),
+    /// # )?;
+    /// for address in 0x08..0x78 {
+    ///     if i2c.probe(address)? {
+    ///         println!("Found device at {address:#04x}");
+    ///     }
+    /// }
+    /// # {after_snippet}
+    /// ```
+    #[instability::unstable]
+    pub fn probe<A: Into<I2cAddress>>(&mut self, address: A) -> Result<bool, Error> {
+        probe_result(self.write(address, &[]))
+    }
+
+    #[procmacros::doc_replace]
+    /// Clears the bus and resets the I2C controller.
+    ///
+    /// If a transaction ended incorrectly, a device may keep driving SDA low
+    /// and block the bus. This function sends up to 9 clock pulses on SCL
+    /// until the device releases SDA, and then issues a STOP condition.
+    ///
+    /// The driver already does this automatically when a transaction fails
+    /// with [`Error::Timeout`] or [`Error::BusTimeout`]. This function can be
+    /// used to recover the bus explicitly, for example after a device was
+    /// reset mid-transaction.
+    ///
+    /// ## Example
+    ///
+    /// ```rust, no_run
+    /// # {before_snippet}
+    /// use esp_hal::i2c::master::{Config, I2c};
+    /// # let mut i2c = I2c::new(
+    /// #   peripherals.I2C0,
+    /// #   Config::default(),
+    /// # )?;
+    /// # const DEVICE_ADDR: u8 = 0x77;
+    /// let mut data = [0u8; 22];
+    /// if i2c.write_read(DEVICE_ADDR, &[0xaa], &mut data).is_err() {
+    ///     i2c.clear_bus();
+    /// }
+    /// # {after_snippet}
+    /// ```
+    #[instability::unstable]
+    pub fn clear_bus(&mut self) {
+        self.driver().clear_bus_blocking(true);
+    }
+
     #[procmacros::doc_replace]
     /// Applies a new configuration.
     ///
@@ -1847,7 +2014,7 @@ impl Driver<'_> {
             scl_stop_setup_time,
             scl_start_hold_time,
             scl_stop_hold_time,
-            timeout.apb_cycles(half_cycle)?,
+            timeout.apb_cycles(half_cycle, bus_freq)?,
         )?;
 
         Ok(())
@@ -1902,7 +2069,7 @@ impl Driver<'_> {
             scl_stop_setup_time,
             scl_start_hold_time,
             scl_stop_hold_time,
-            timeout.apb_cycles(half_cycle)?,
+            timeout.apb_cycles(half_cycle, bus_freq)?,
         )?;
 
         Ok(())
@@ -1971,7 +2138,7 @@ impl Driver<'_> {
             scl_stop_setup_time,
             scl_start_hold_time,
             scl_stop_hold_time,
-            timeout.apb_cycles(half_cycle)?,
+            timeout.apb_cycles(half_cycle, bus_freq)?,
         )?;
 
         Ok(())
@@ -2305,14 +2472,14 @@ impl Driver<'_> {
         #[cfg(i2c_master_has_fsm_timeouts)]
         {
             if r.scl_st_to().bit_is_set() {
-                return Err(Error::Timeout);
+                return Err(Error::BusTimeout);
             }
             if r.scl_main_st_to().bit_is_set() {
-                return Err(Error::Timeout);
+                return Err(Error::BusTimeout);
             }
         }
         if r.time_out().bit_is_set() {
-            return Err(Error::Timeout);
+            return Err(Error::BusTimeout);
         }
 
         Ok(())
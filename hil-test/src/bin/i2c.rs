@@ -13,6 +13,7 @@ use esp_hal::{
     Blocking,
     i2c::master::{
         AcknowledgeCheckFailedReason,
+        BusTimeout,
         Config,
         Error,
         I2c,
@@ -122,6 +123,16 @@ mod tests {
         assert_eq!(ctx.i2c.write(DUT_ADDRESS, &[]), Ok(()));
     }
 
+    #[test]
+    fn probe_finds_only_existing_device(mut ctx: Context) {
+        assert_eq!(ctx.i2c.probe(NON_EXISTENT_ADDRESS), Ok(false));
+        assert_eq!(ctx.i2c.probe(DUT_ADDRESS), Ok(true));
+        assert_eq!(
+            ctx.i2c.probe(0x80),
+            Err(Error::AddressInvalid(I2cAddress::SevenBit(0x80)))
+        );
+    }
+
     #[test]
     fn test_read_cali(mut ctx: Context) {
         let mut read_data = [0u8; 22];
@@ -176,6 +187,14 @@ mod tests {
         assert_eq!(i2c.write_async(DUT_ADDRESS, &[]).await, Ok(()));
     }
 
+    #[test]
+    async fn async_probe_finds_only_existing_device(ctx: Context) {
+        let mut i2c = ctx.i2c.into_async();
+
+        assert_eq!(i2c.probe_async(NON_EXISTENT_ADDRESS).await, Ok(false));
+        assert_eq!(i2c.probe_async(DUT_ADDRESS).await, Ok(true));
+    }
+
     #[test]
     async fn async_test_read_cali(ctx: Context) {
         let mut i2c = ctx.i2c.into_async();
@@ -313,6 +332,53 @@ mod tests {
             .expect_err("Expected timeout error");
     }
 
+    #[test]
+    fn test_bus_timeout_when_scl_kept_low(mut ctx: Context) {
+        // The software timeout is much longer than the bus timeout, so that the hardware
+        // timeout always fires first.
+        ctx.i2c
+            .apply_config(
+                &Config::default()
+                    .with_timeout(BusTimeout::Duration(time::Duration::from_millis(1)))
+                    .with_software_timeout(SoftwareTimeout::Transaction(
+                        time::Duration::from_secs(1),
+                    )),
+            )
+            .unwrap();
+
+        esp_hal::gpio::InputSignal::I2CEXT0_SCL.connect_to(&esp_hal::gpio::Level::Low);
+
+        let mut read_data = [0u8; 22];
+        assert_eq!(
+            ctx.i2c
+                .write_read(DUT_ADDRESS, READ_DATA_COMMAND, &mut read_data),
+            Err(Error::BusTimeout)
+        );
+    }
+
+    #[test]
+    async fn async_test_bus_timeout_when_scl_kept_low(ctx: Context) {
+        let mut i2c = ctx.i2c.into_async();
+
+        // The software timeout is much longer than the bus timeout, so that the hardware
+        // timeout always fires first.
+        i2c.apply_config(
+            &Config::default()
+                .with_timeout(BusTimeout::Duration(time::Duration::from_millis(1)))
+                .with_software_timeout(SoftwareTimeout::Transaction(time::Duration::from_secs(1))),
+        )
+        .unwrap();
+
+        esp_hal::gpio::InputSignal::I2CEXT0_SCL.connect_to(&esp_hal::gpio::Level::Low);
+
+        let mut read_data = [0u8; 22];
+        assert_eq!(
+            i2c.write_read_async(DUT_ADDRESS, READ_DATA_COMMAND, &mut read_data)
+                .await,
+            Err(Error::BusTimeout)
+        );
+    }
+
     #[test]
     async fn async_test_timeout_when_scl_kept_low(ctx: Context) {
         let mut i2c = ctx.i2c.into_async();
@@ -332,6 +398,32 @@ mod tests {
             .expect_err("Expected timeout error");
     }
 
+    #[test]
+    fn read_succeeds_after_clear_bus(mut ctx: Context) {
+        ctx.i2c.clear_bus();
+
+        let mut read_data = [0u8; 22];
+        ctx.i2c
+            .write_read(DUT_ADDRESS, READ_DATA_COMMAND, &mut read_data)
+            .unwrap();
+
+        assert_ne!(read_data, [0u8; 22])
+    }
+
+    #[test]
+    async fn async_read_succeeds_after_clear_bus(ctx: Context) {
+        let mut i2c = ctx.i2c.into_async();
+
+        i2c.clear_bus_async().await;
+
+        let mut read_data = [0u8; 22];
+        i2c.write_read_async(DUT_ADDRESS, READ_DATA_COMMAND, &mut read_data)
+            .await
+            .unwrap();
+
+        assert_ne!(read_data, [0u8; 22])
+    }
+
     #[test]
     #[timeout(10)]
     async fn no_timeout_when_preempted_for_long_time(ctx: Context) {